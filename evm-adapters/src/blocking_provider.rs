@@ -1,23 +1,209 @@
 use ethers::{
     prelude::BlockNumber,
-    providers::Middleware,
-    types::{Address, Block, BlockId, Bytes, TxHash, H256, U256, U64},
+    providers::{Http, Middleware, Provider},
+    types::{Address, Block, BlockId, Bytes, EIP1186ProofResponse, FeeHistory, TxHash, H256, U256, U64},
+    utils::keccak256,
+};
+use rand::Rng;
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    collections::HashMap,
+    sync::{Arc, OnceLock},
+    time::Duration,
 };
 use tokio::runtime::{Handle, Runtime};
 
+/// Retry policy for transient RPC failures (rate limiting, dropped connections), used by
+/// [`BlockingProvider::new_with_retry`].
+///
+/// At this abstraction level errors reach us through [`Middleware`]'s provider error type rather
+/// than a raw HTTP response, so there's no structured access to a `Retry-After` header. We still
+/// make a best-effort attempt at honoring it: if the error's message contains a `Retry-After`
+/// value (some providers, including ethers' `Http` transport, fold the response headers/body
+/// into the error text), [`parse_retry_after`] extracts it and that delay is used instead of the
+/// computed backoff for that attempt. When no such hint is present, the delay is the computed
+/// backoff: `initial_backoff` before the first retry, multiplied by `backoff_multiplier` after
+/// each retry. A random jitter of up to 100ms is added to every delay.
+#[derive(Debug, Clone, Copy)]
+pub struct RetryConfig {
+    pub max_retries: usize,
+    pub initial_backoff: Duration,
+    pub backoff_multiplier: f64,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self { max_retries: 5, initial_backoff: Duration::from_millis(250), backoff_multiplier: 2.0 }
+    }
+}
+
+/// Crude heuristic for whether an RPC error looks transient (rate limiting, connection resets)
+/// rather than a real application error (a revert, invalid params), since errors reach us as
+/// opaque, provider-specific types rather than a structured reason code.
+fn looks_retryable(err: &(dyn std::error::Error + 'static)) -> bool {
+    let message = err.to_string().to_lowercase();
+    message.contains("429") ||
+        message.contains("rate limit") ||
+        message.contains("too many requests") ||
+        message.contains("connection reset") ||
+        message.contains("connection closed") ||
+        message.contains("timed out")
+}
+
+/// Best-effort extraction of a `Retry-After` hint from an error's message, honoring the header
+/// when the transport surfaces it (either as `retry-after: <seconds>` or a bare `retry-after=N`).
+/// `Retry-After` is defined in seconds only (HTTP date values aren't handled), per RFC 7231.
+fn parse_retry_after(err: &(dyn std::error::Error + 'static)) -> Option<Duration> {
+    let message = err.to_string().to_lowercase();
+    let after = message.find("retry-after")?;
+    let tail = message[after + "retry-after".len()..].trim_start_matches([':', '=', ' ']);
+    let digits: String = tail.chars().take_while(|c| c.is_ascii_digit()).collect();
+    digits.parse().ok().map(Duration::from_secs)
+}
+
+/// The Ethereum node implementation a [`BlockingProvider`] is talking to, detected from
+/// `web3_clientVersion`. Downstream code can branch on this instead of blindly issuing calls
+/// some clients don't implement — e.g. choosing `debug_*` vs `trace_*` tracing namespaces, or
+/// knowing whether archive-state reads at arbitrary historical blocks are available.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NodeClient {
+    Geth,
+    Erigon,
+    Nethermind,
+    Besu,
+    OpenEthereum,
+    Reth,
+    Unknown,
+}
+
+impl NodeClient {
+    /// Parses the leading token of a `web3_clientVersion` response, e.g. `"Geth/v1.10.26-..."`.
+    fn parse(client_version: &str) -> Self {
+        match client_version.split('/').next().unwrap_or_default().to_lowercase().as_str() {
+            "geth" => Self::Geth,
+            "erigon" => Self::Erigon,
+            "nethermind" => Self::Nethermind,
+            "besu" => Self::Besu,
+            "openethereum" | "parity-ethereum" => Self::OpenEthereum,
+            "reth" => Self::Reth,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+/// A block reference for state queries, per EIP-1898.
+///
+/// Unlike [`BlockId`], [`BlockSpec::Hash`] can additionally assert that the hash must still be
+/// part of the canonical chain, so callers that pin reads to a specific historical block hash
+/// fail loudly if that block was re-orged out instead of silently falling back to `latest`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlockSpec {
+    Number(BlockNumber),
+    Hash { hash: H256, require_canonical: bool },
+}
+
+impl BlockSpec {
+    fn as_block_id(&self) -> BlockId {
+        match *self {
+            Self::Number(number) => BlockId::Number(number),
+            Self::Hash { hash, .. } => BlockId::Hash(hash),
+        }
+    }
+
+    fn require_canonical(&self) -> bool {
+        matches!(self, Self::Hash { require_canonical: true, .. })
+    }
+}
+
+impl From<BlockNumber> for BlockSpec {
+    fn from(number: BlockNumber) -> Self {
+        Self::Number(number)
+    }
+}
+
+impl From<BlockId> for BlockSpec {
+    fn from(block_id: BlockId) -> Self {
+        match block_id {
+            BlockId::Number(number) => Self::Number(number),
+            BlockId::Hash(hash) => Self::Hash { hash, require_canonical: false },
+        }
+    }
+}
+
+impl From<H256> for BlockSpec {
+    fn from(hash: H256) -> Self {
+        Self::Hash { hash, require_canonical: false }
+    }
+}
+
+/// The untagged `eth_getProof`/`eth_call`-style block parameter sent on the wire: either the
+/// usual `BlockId` (a tag or a bare hash), or, when [`BlockSpec::Hash::require_canonical`] is
+/// set, the EIP-1898 `{ blockHash, requireCanonical }` object.
+#[derive(Serialize, Clone)]
+#[serde(untagged)]
+enum BlockParam {
+    Id(BlockId),
+    Canonical {
+        #[serde(rename = "blockHash")]
+        block_hash: H256,
+        #[serde(rename = "requireCanonical")]
+        require_canonical: bool,
+    },
+}
+
+impl From<Option<BlockSpec>> for BlockParam {
+    fn from(spec: Option<BlockSpec>) -> Self {
+        match spec {
+            None => Self::Id(BlockId::Number(BlockNumber::Latest)),
+            Some(spec) if spec.require_canonical() => {
+                let BlockSpec::Hash { hash, .. } = spec else { unreachable!() };
+                Self::Canonical { block_hash: hash, require_canonical: true }
+            }
+            Some(spec) => Self::Id(spec.as_block_id()),
+        }
+    }
+}
+
+/// Returned when a [`BlockSpec::Hash`] with `require_canonical: true` could not be resolved,
+/// because the node doesn't know the hash or the block was re-orged out of the canonical chain.
+#[derive(Debug)]
+pub struct NonCanonicalBlockError {
+    pub block_hash: H256,
+}
+
+impl std::fmt::Display for NonCanonicalBlockError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "block {:?} is not canonical (unknown to the node or re-orged out)", self.block_hash)
+    }
+}
+
+impl std::error::Error for NonCanonicalBlockError {}
+
+/// Crude heuristic for whether an `eth_getProof`/`eth_getBalance`-style error is the node
+/// rejecting a non-canonical `requireCanonical` block hash, since JSON-RPC errors are surfaced to
+/// us as opaque, provider-specific error types rather than a structured reason code.
+fn looks_like_non_canonical_error(err: &(dyn std::error::Error + 'static)) -> bool {
+    let message = err.to_string().to_lowercase();
+    message.contains("not found") || message.contains("not canonical") || message.contains("unknown block")
+}
+
 #[derive(Debug)]
 /// Blocking wrapper around an Ethers middleware, for use in synchronous contexts
 /// (powered by a tokio runtime)
 pub struct BlockingProvider<M> {
     provider: M,
-    runtime: Option<Runtime>,
+    runtime: Option<Arc<Runtime>>,
+    node_client: Arc<OnceLock<NodeClient>>,
+    retry: Option<RetryConfig>,
 }
 
 impl<M: Clone> Clone for BlockingProvider<M> {
     fn clone(&self) -> Self {
         Self {
             provider: self.provider.clone(),
-            runtime: self.runtime.as_ref().map(|_| Runtime::new().unwrap()),
+            runtime: self.runtime.clone(),
+            node_client: self.node_client.clone(),
+            retry: self.retry,
         }
     }
 }
@@ -26,9 +212,38 @@ impl<M: Middleware> BlockingProvider<M>
 where
     M::Error: 'static,
 {
+    /// Floor for [`Self::estimate_eip1559_fees`]'s max fee when fee history is empty (20 gwei).
+    const FALLBACK_MAX_FEE_PER_GAS: U256 = U256([20_000_000_000, 0, 0, 0]);
+    /// Floor for [`Self::estimate_eip1559_fees`]'s tip when fee history is empty (1 gwei).
+    const FALLBACK_MAX_PRIORITY_FEE_PER_GAS: U256 = U256([1_000_000_000, 0, 0, 0]);
+
     pub fn new(provider: M) -> Self {
-        let runtime = Handle::try_current().is_err().then(|| Runtime::new().unwrap());
-        Self { provider, runtime }
+        let runtime = Handle::try_current().is_err().then(|| Arc::new(Runtime::new().unwrap()));
+        Self { provider, runtime, node_client: Arc::new(OnceLock::new()), retry: None }
+    }
+
+    /// Like [`Self::new`], but transient RPC failures (rate limiting, dropped connections) are
+    /// retried with exponential backoff and jitter per `retry` instead of failing the call
+    /// immediately. Non-retryable errors (reverts, invalid params) still propagate on the first
+    /// attempt. This only covers the raw-request path used by the EIP-1898-aware state getters
+    /// below (`get_balance`, `get_code`, `get_storage_at`, `get_transaction_count`,
+    /// `get_account`, `get_accounts`) and by `get_accounts_batched`'s batch POST; it does not
+    /// retry calls made directly through the underlying [`Middleware`].
+    pub fn new_with_retry(provider: M, retry: RetryConfig) -> Self {
+        Self { retry: Some(retry), ..Self::new(provider) }
+    }
+
+    /// Detects the node implementation behind this provider via `web3_clientVersion`, caching
+    /// the result so it's only queried once (and shared with any clones of this provider).
+    pub fn node_client(&self) -> eyre::Result<NodeClient> {
+        if let Some(client) = self.node_client.get() {
+            return Ok(*client)
+        }
+        let version = self.block_on(self.provider.client_version())?;
+        let client = NodeClient::parse(&version);
+        // Lost races just mean a clone beat us to it with the same answer; either is fine to use.
+        let _ = self.node_client.set(client);
+        Ok(client)
     }
 
     fn block_on<F: std::future::Future>(&self, f: F) -> F::Output {
@@ -38,6 +253,85 @@ where
         }
     }
 
+    /// Issues a raw JSON-RPC request whose last positional param is the block reference,
+    /// serialized as the EIP-1898 `requireCanonical` object when `block` asks for it. This is the
+    /// common path for the single-param state-reading methods below, since the convenience
+    /// methods on [`Middleware`] only know how to serialize a plain [`BlockId`] and can't express
+    /// `requireCanonical`. `params` excludes the block reference; it's appended here.
+    /// (`get_storage_at` takes two params before the block, so it builds its own tuple and calls
+    /// [`Self::request_with_block_param`] directly.)
+    async fn request_with_block<
+        T: std::fmt::Debug + Serialize + Clone + Send + Sync,
+        R: Serialize + DeserializeOwned + std::fmt::Debug + Send,
+    >(
+        &self,
+        method: &'static str,
+        params: T,
+        block: Option<BlockSpec>,
+    ) -> eyre::Result<R> {
+        self.request_with_block_param(method, (params, BlockParam::from(block)), block).await
+    }
+
+    /// Issues `method`/`params` through [`Provider::request`], retrying on transient failures and
+    /// translating non-canonical block errors via [`Self::with_retry`].
+    async fn request_with_block_param<
+        T: std::fmt::Debug + Serialize + Clone + Send + Sync,
+        R: Serialize + DeserializeOwned + std::fmt::Debug + Send,
+    >(
+        &self,
+        method: &'static str,
+        params: T,
+        block: Option<BlockSpec>,
+    ) -> eyre::Result<R> {
+        self.with_retry(block, || self.provider.provider().request(method, params.clone())).await
+    }
+
+    /// Runs `attempt`, retrying on transient failures per `self.retry` by calling it again
+    /// (`attempt` is an `FnMut` rather than a single future so each retry can rebuild its
+    /// request) with exponential backoff and jitter between attempts, honoring a `Retry-After`
+    /// hint via [`parse_retry_after`] when the error exposes one. A [`BlockSpec::Hash`] with
+    /// `require_canonical: true` whose error looks like a non-canonical-block rejection is
+    /// translated to [`NonCanonicalBlockError`] immediately, bypassing retry. Other errors that
+    /// don't look retryable propagate immediately, and a retryable error propagates once
+    /// `max_retries` retries have been used up.
+    async fn with_retry<T, E, Fut>(
+        &self,
+        block: Option<BlockSpec>,
+        mut attempt: impl FnMut() -> Fut,
+    ) -> eyre::Result<T>
+    where
+        Fut: std::future::Future<Output = Result<T, E>>,
+        E: std::error::Error + Send + Sync + 'static,
+    {
+        let mut backoff = self.retry.map(|retry| retry.initial_backoff);
+        let mut attempt_count = 0;
+
+        loop {
+            match attempt().await {
+                Ok(value) => return Ok(value),
+                Err(err) => {
+                    if let Some(BlockSpec::Hash { hash, require_canonical: true }) = block {
+                        if looks_like_non_canonical_error(&err) {
+                            return Err(NonCanonicalBlockError { block_hash: hash }.into())
+                        }
+                    }
+
+                    let Some(retry) = self.retry else { return Err(err.into()) };
+                    if attempt_count >= retry.max_retries || !looks_retryable(&err) {
+                        return Err(err.into())
+                    }
+
+                    let computed_backoff = backoff.expect("retry.is_some() implies backoff.is_some()");
+                    let delay = parse_retry_after(&err).unwrap_or(computed_backoff);
+                    let jitter = Duration::from_millis(rand::thread_rng().gen_range(0..100));
+                    tokio::time::sleep(delay + jitter).await;
+                    backoff = Some(computed_backoff.mul_f64(retry.backoff_multiplier));
+                    attempt_count += 1;
+                }
+            }
+        }
+    }
+
     pub fn block_and_chainid(
         &self,
         block_id: Option<impl Into<BlockId>>,
@@ -55,12 +349,12 @@ where
     pub fn get_account(
         &self,
         address: Address,
-        block_id: Option<BlockId>,
+        block: Option<BlockSpec>,
     ) -> eyre::Result<(U256, U256, Bytes)> {
         let f = async {
-            let balance = self.provider.get_balance(address, block_id);
-            let nonce = self.provider.get_transaction_count(address, block_id);
-            let code = self.provider.get_code(address, block_id);
+            let balance = self.request_with_block("eth_getBalance", address, block);
+            let nonce = self.request_with_block("eth_getTransactionCount", address, block);
+            let code = self.request_with_block("eth_getCode", address, block);
             tokio::try_join!(balance, nonce, code)
         };
         let (balance, nonce, code) = self.block_on(f)?;
@@ -68,32 +362,462 @@ where
         Ok((nonce, balance, code))
     }
 
+    /// Fetches `(nonce, balance, code)` for many addresses at once by dispatching all the
+    /// per-address requests concurrently rather than serially `block_on`-ing each one in turn.
+    ///
+    /// This is a transport-agnostic fallback, not a real JSON-RPC batch: `M` is generic over any
+    /// middleware/transport, and nothing in [`Middleware`]'s JSON-RPC client abstraction lets us
+    /// multiplex many calls onto a single request for an arbitrary transport. It still avoids paying
+    /// `addresses.len()` round trips serially, but for a true single-round-trip batch (packed
+    /// into one request body, demultiplexed by response `id`) against a concrete `Provider<Http>`,
+    /// use [`BlockingProvider::get_accounts_batched`] instead. The result preserves the order of
+    /// `addresses` since `try_join_all` resolves futures in the order they were given, not the
+    /// order responses arrive.
+    pub fn get_accounts(
+        &self,
+        addresses: &[Address],
+        block: Option<BlockSpec>,
+    ) -> eyre::Result<Vec<(U256, U256, Bytes)>> {
+        let f = async {
+            let requests = addresses.iter().map(|address| async move {
+                let balance = self.request_with_block("eth_getBalance", address, block);
+                let nonce = self.request_with_block("eth_getTransactionCount", address, block);
+                let code = self.request_with_block("eth_getCode", address, block);
+                tokio::try_join!(balance, nonce, code)
+            });
+            futures::future::try_join_all(requests).await
+        };
+        let accounts = self.block_on(f)?;
+
+        Ok(accounts.into_iter().map(|(balance, nonce, code)| (nonce, balance, code)).collect())
+    }
+
     pub fn get_block_number(&self) -> Result<U64, M::Error> {
         self.block_on(self.provider.get_block_number())
     }
 
-    pub fn get_balance(&self, address: Address, block: Option<BlockId>) -> Result<U256, M::Error> {
-        self.block_on(self.provider.get_balance(address, block))
+    pub fn get_balance(&self, address: Address, block: Option<BlockSpec>) -> eyre::Result<U256> {
+        self.block_on(self.request_with_block("eth_getBalance", address, block))
     }
 
     pub fn get_transaction_count(
         &self,
         address: Address,
-        block: Option<BlockId>,
-    ) -> Result<U256, M::Error> {
-        self.block_on(self.provider.get_transaction_count(address, block))
+        block: Option<BlockSpec>,
+    ) -> eyre::Result<U256> {
+        self.block_on(self.request_with_block("eth_getTransactionCount", address, block))
     }
 
-    pub fn get_code(&self, address: Address, block: Option<BlockId>) -> Result<Bytes, M::Error> {
-        self.block_on(self.provider.get_code(address, block))
+    pub fn get_code(&self, address: Address, block: Option<BlockSpec>) -> eyre::Result<Bytes> {
+        self.block_on(self.request_with_block("eth_getCode", address, block))
     }
 
     pub fn get_storage_at(
         &self,
         address: Address,
         slot: H256,
+        block: Option<BlockSpec>,
+    ) -> eyre::Result<H256> {
+        let params = (address, slot, BlockParam::from(block));
+        self.block_on(self.request_with_block_param("eth_getStorageAt", params, block))
+    }
+
+    /// Fetches EIP-1559 fee history (`eth_feeHistory`) for the `block_count` blocks ending at
+    /// `newest_block`, sampling `reward_percentiles` of each block's priority fees.
+    pub fn get_fee_history(
+        &self,
+        block_count: U256,
+        newest_block: BlockNumber,
+        reward_percentiles: &[f64],
+    ) -> Result<FeeHistory, M::Error> {
+        self.block_on(self.provider.fee_history(block_count, newest_block, reward_percentiles))
+    }
+
+    /// Derives a suggested `(max_fee_per_gas, max_priority_fee_per_gas)` for an EIP-1559
+    /// transaction from recent fee history: the tip is the median of the requested-percentile
+    /// priority-fee samples over the last 10 blocks, and the max fee is `2 * pending_base_fee +
+    /// tip`. Falls back to [`Self::FALLBACK_MAX_FEE_PER_GAS`] /
+    /// [`Self::FALLBACK_MAX_PRIORITY_FEE_PER_GAS`] when history is empty, e.g. on a pre-London
+    /// chain.
+    pub fn estimate_eip1559_fees(&self) -> eyre::Result<(U256, U256)> {
+        let history = self.get_fee_history(U256::from(10u64), BlockNumber::Latest, &[50.0])?;
+
+        let mut tips: Vec<U256> =
+            history.reward.iter().filter_map(|percentiles| percentiles.first().copied()).collect();
+        if tips.is_empty() {
+            return Ok((Self::FALLBACK_MAX_FEE_PER_GAS, Self::FALLBACK_MAX_PRIORITY_FEE_PER_GAS))
+        }
+        tips.sort();
+        let tip = tips[tips.len() / 2];
+
+        let pending_base_fee = *history
+            .base_fee_per_gas
+            .last()
+            .ok_or_else(|| eyre::eyre!("fee history response had no base fee samples"))?;
+        let max_fee = pending_base_fee.saturating_mul(U256::from(2u64)).saturating_add(tip);
+
+        Ok((max_fee, tip))
+    }
+
+    /// Fetches an EIP-1186 account and storage proof (`eth_getProof`) for `address` and `slots`.
+    pub fn get_proof(
+        &self,
+        address: Address,
+        slots: &[H256],
         block: Option<BlockId>,
-    ) -> Result<H256, M::Error> {
-        self.block_on(self.provider.get_storage_at(address, slot, block))
+    ) -> Result<EIP1186ProofResponse, M::Error> {
+        self.block_on(self.provider.get_proof(address, slots.to_vec(), block))
+    }
+
+    /// Trustlessly verifies an [`EIP1186ProofResponse`] against `state_root`, the state root of
+    /// the block the proof was fetched at.
+    ///
+    /// This walks the returned account proof as a Merkle-Patricia trie keyed on
+    /// `keccak256(address)`, confirming every node hashes to the value referenced by its parent,
+    /// and checks that the terminal leaf encodes `[nonce, balance, storageHash, codeHash]`
+    /// matching the values in `proof`. Each storage proof is then verified the same way against
+    /// `proof.storage_hash`, keyed on `keccak256(slot)`. Use this before trusting state pulled
+    /// from an untrusted RPC node instead of taking `get_storage_at`/`get_account` on faith.
+    pub fn verify_proof(&self, proof: &EIP1186ProofResponse, state_root: H256) -> eyre::Result<()> {
+        mpt::verify_account_proof(proof, state_root)?;
+        for storage_proof in &proof.storage_proof {
+            mpt::verify_storage_proof(storage_proof, proof.storage_hash)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize)]
+struct BatchCall<'a> {
+    jsonrpc: &'static str,
+    id: u64,
+    method: &'a str,
+    params: (Address, BlockParam),
+}
+
+#[derive(serde::Deserialize)]
+struct BatchResponse {
+    id: u64,
+    #[serde(default)]
+    result: Option<serde_json::Value>,
+    #[serde(default)]
+    error: Option<serde_json::Value>,
+}
+
+/// The `eth_getBalance`/`eth_getTransactionCount`/`eth_getCode` calls `get_accounts_batched`
+/// packs per address, in the fixed order their response `id`s are derived from.
+const ACCOUNT_BATCH_METHODS: [&str; 3] =
+    ["eth_getBalance", "eth_getTransactionCount", "eth_getCode"];
+
+impl BlockingProvider<Provider<Http>> {
+    /// Fetches `(nonce, balance, code)` for many addresses in a single JSON-RPC batch: one POST
+    /// whose body is a JSON array of `eth_getBalance`/`eth_getTransactionCount`/`eth_getCode`
+    /// request objects (one round trip, unlike [`Self::get_accounts`]'s concurrent-but-separate
+    /// requests), with the response array demultiplexed by each entry's `id` since a batch
+    /// response can come back in a different order than the requests were sent.
+    ///
+    /// `Middleware`/`JsonRpcClient` has no batch primitive to build this on top of generically,
+    /// so this is only available for the concrete `Provider<Http>` transport, where we can reach
+    /// the RPC URL directly and POST the batch envelope ourselves.
+    pub fn get_accounts_batched(
+        &self,
+        addresses: &[Address],
+        block: Option<BlockSpec>,
+    ) -> eyre::Result<Vec<(U256, U256, Bytes)>> {
+        let block_param = BlockParam::from(block);
+        let methods = ACCOUNT_BATCH_METHODS;
+
+        let batch: Vec<BatchCall> = addresses
+            .iter()
+            .enumerate()
+            .flat_map(|(i, address)| {
+                let block_param = &block_param;
+                methods.iter().enumerate().map(move |(m, method)| BatchCall {
+                    jsonrpc: "2.0",
+                    id: (i * methods.len() + m) as u64,
+                    method,
+                    params: (*address, block_param.clone()),
+                })
+            })
+            .collect();
+
+        let url = self.provider.url().clone();
+        let client = reqwest::Client::new();
+        // Route through `with_retry` so a failed batch POST (a transport error, or an HTTP error
+        // status such as 429) gets the same retry-with-backoff as every other request in this
+        // file, rather than silently opting out just because it bypasses `Provider::request`.
+        // Errors reported for individual calls inside a batch response are not retried; they are
+        // returned below, translated to `NonCanonicalBlockError` like everywhere else.
+        let responses: Vec<BatchResponse> = self
+            .block_on(self.with_retry(block, || async {
+                client
+                    .post(url.clone())
+                    .json(&batch)
+                    .send()
+                    .await?
+                    .error_for_status()?
+                    .json::<Vec<BatchResponse>>()
+                    .await
+            }))?;
+
+        let mut by_id: HashMap<u64, serde_json::Value> = HashMap::with_capacity(responses.len());
+        for response in responses {
+            if let Some(error) = response.error {
+                let err = eyre::eyre!("batch call {} failed: {error}", response.id);
+                if let Some(BlockSpec::Hash { hash, require_canonical: true }) = block {
+                    if looks_like_non_canonical_error(&*err) {
+                        return Err(NonCanonicalBlockError { block_hash: hash }.into())
+                    }
+                }
+                return Err(err)
+            }
+            by_id.insert(response.id, response.result.unwrap_or(serde_json::Value::Null));
+        }
+
+        addresses
+            .iter()
+            .enumerate()
+            .map(|(i, _)| {
+                let base = (i * methods.len()) as u64;
+                let mut value_for = |offset: u64| -> eyre::Result<serde_json::Value> {
+                    by_id
+                        .remove(&(base + offset))
+                        .ok_or_else(|| eyre::eyre!("missing batch response for call {}", base + offset))
+                };
+                let balance: U256 = serde_json::from_value(value_for(0)?)?;
+                let nonce: U256 = serde_json::from_value(value_for(1)?)?;
+                let code: Bytes = serde_json::from_value(value_for(2)?)?;
+                Ok((nonce, balance, code))
+            })
+            .collect()
+    }
+}
+
+/// Minimal Merkle-Patricia trie proof verification for EIP-1186 `eth_getProof` responses.
+mod mpt {
+    use super::{keccak256, EIP1186ProofResponse, H256, U256};
+    use ethers::types::StorageProof;
+    use rlp::Rlp;
+
+    pub(super) fn verify_account_proof(
+        proof: &EIP1186ProofResponse,
+        state_root: H256,
+    ) -> eyre::Result<()> {
+        let key = keccak_nibbles(proof.address.as_bytes());
+        let value = verify_proof(state_root, &key, &proof.account_proof)?;
+
+        let rlp = Rlp::new(&value);
+        if rlp.item_count()? != 4 {
+            eyre::bail!("account leaf does not encode [nonce, balance, storageHash, codeHash]");
+        }
+        let nonce: U256 = rlp.val_at(0)?;
+        let balance: U256 = rlp.val_at(1)?;
+        let storage_hash: H256 = rlp.val_at(2)?;
+        let code_hash: H256 = rlp.val_at(3)?;
+
+        if nonce != proof.nonce.as_u64().into() ||
+            balance != proof.balance ||
+            storage_hash != proof.storage_hash ||
+            code_hash != proof.code_hash
+        {
+            eyre::bail!("account proof leaf does not match the claimed account state");
+        }
+        Ok(())
+    }
+
+    pub(super) fn verify_storage_proof(
+        storage_proof: &StorageProof,
+        storage_hash: H256,
+    ) -> eyre::Result<()> {
+        let mut slot_bytes = [0u8; 32];
+        storage_proof.key.to_big_endian(&mut slot_bytes);
+        let key = keccak_nibbles(&slot_bytes);
+
+        if storage_proof.proof.is_empty() {
+            // An empty proof only ever attests to a slot holding its default (zero) value.
+            if storage_proof.value.is_zero() {
+                return Ok(())
+            }
+            eyre::bail!("empty storage proof for a non-zero claimed value");
+        }
+
+        let value = verify_proof(storage_hash, &key, &storage_proof.proof)?;
+        let rlp = Rlp::new(&value);
+        let claimed: U256 = rlp.as_val()?;
+        if claimed != storage_proof.value {
+            eyre::bail!("storage proof leaf does not match the claimed slot value");
+        }
+        Ok(())
+    }
+
+    /// Nibble path into the trie for a given key: the hex digits of `keccak256(key)`.
+    fn keccak_nibbles(key: &[u8]) -> Vec<u8> {
+        keccak256(key).iter().flat_map(|byte| [byte >> 4, byte & 0x0f]).collect()
+    }
+
+    /// Walks `proof` from `root` following `nibbles`, checking every node's hash chains to its
+    /// parent's reference, and returns the raw RLP value stored at the terminal leaf.
+    fn verify_proof(root: H256, nibbles: &[u8], proof: &[ethers::types::Bytes]) -> eyre::Result<Vec<u8>> {
+        let mut expected_hash = root.as_bytes().to_vec();
+        let mut offset = 0;
+
+        for (i, node) in proof.iter().enumerate() {
+            if keccak256(node.as_ref()).as_slice() != expected_hash.as_slice() {
+                eyre::bail!("proof node {i} hash does not match the hash referenced by its parent");
+            }
+
+            let rlp = Rlp::new(node);
+            match rlp.item_count()? {
+                17 => {
+                    if offset == nibbles.len() {
+                        let value: Vec<u8> = rlp.at(16)?.data()?.to_vec();
+                        return Ok(value)
+                    }
+                    let nibble = nibbles[offset] as usize;
+                    offset += 1;
+                    expected_hash = child_reference(&rlp.at(nibble)?)?;
+                }
+                2 => {
+                    let (is_leaf, shared) = decode_compact_path(rlp.at(0)?.data()?)?;
+                    if nibbles[offset..].get(..shared.len()) != Some(shared.as_slice()) {
+                        eyre::bail!("proof node {i} path does not match the lookup key");
+                    }
+                    offset += shared.len();
+                    if is_leaf {
+                        return Ok(rlp.at(1)?.data()?.to_vec())
+                    }
+                    expected_hash = child_reference(&rlp.at(1)?)?;
+                }
+                n => eyre::bail!("proof node {i} has an unexpected item count ({n})"),
+            }
+        }
+
+        eyre::bail!("proof was exhausted before reaching a terminal leaf")
+    }
+
+    /// The hash a parent node uses to reference a child: the child's data directly if it's
+    /// already a 32-byte hash, otherwise the hash of the child's own RLP encoding (nodes smaller
+    /// than 32 bytes are embedded inline rather than referenced by hash).
+    fn child_reference(child: &Rlp) -> eyre::Result<Vec<u8>> {
+        if child.is_data() {
+            let data = child.data()?;
+            if data.len() == 32 {
+                return Ok(data.to_vec())
+            }
+        }
+        Ok(keccak256(child.as_raw()).to_vec())
+    }
+
+    /// Decodes the hex-prefix-encoded nibble path shared by leaf/extension nodes, per the
+    /// Ethereum Yellow Paper appendix D. Returns `(is_leaf, nibbles)`.
+    ///
+    /// `path` comes from an untrusted RPC node, so a malformed (empty) compact path is a data
+    /// error to reject, not a condition the caller already ruled out.
+    fn decode_compact_path(path: &[u8]) -> eyre::Result<(bool, Vec<u8>)> {
+        if path.is_empty() {
+            eyre::bail!("proof node has an empty compact path");
+        }
+
+        let is_leaf = path[0] & 0x20 != 0;
+        let is_odd = path[0] & 0x10 != 0;
+
+        let mut nibbles = Vec::with_capacity(path.len() * 2);
+        if is_odd {
+            nibbles.push(path[0] & 0x0f);
+        }
+        for byte in &path[1..] {
+            nibbles.push(byte >> 4);
+            nibbles.push(byte & 0x0f);
+        }
+        Ok((is_leaf, nibbles))
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::decode_compact_path;
+
+        #[test]
+        fn decode_compact_path_rejects_empty_input() {
+            // Regression test for a panic on malformed/malicious proof nodes (fixed in
+            // e954b1c): an empty compact path used to index `path[0]` unconditionally.
+            assert!(decode_compact_path(&[]).is_err());
+        }
+
+        #[test]
+        fn decode_compact_path_even_leaf() {
+            // 0x20 flags a leaf with an even number of nibbles; no low nibble to keep.
+            let (is_leaf, nibbles) = decode_compact_path(&[0x20, 0x0a, 0xbc]).unwrap();
+            assert!(is_leaf);
+            assert_eq!(nibbles, vec![0, 0xa, 0xb, 0xc]);
+        }
+
+        #[test]
+        fn decode_compact_path_odd_extension() {
+            // 0x10 flags an odd-length extension; the low nibble of the first byte is the first
+            // path nibble.
+            let (is_leaf, nibbles) = decode_compact_path(&[0x1a, 0xbc]).unwrap();
+            assert!(!is_leaf);
+            assert_eq!(nibbles, vec![0xa, 0xb, 0xc]);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn block_param_canonical_serializes_camel_case() {
+        // Regression test: `require_canonical` must rename to `requireCanonical` on the wire, or
+        // every RPC node silently ignores the unknown field and the "fail loudly on reorg"
+        // feature this type exists for fails silently instead.
+        let param = BlockParam::from(Some(BlockSpec::Hash {
+            hash: H256::repeat_byte(0x11),
+            require_canonical: true,
+        }));
+        let value = serde_json::to_value(&param).unwrap();
+        assert_eq!(value["requireCanonical"], serde_json::json!(true));
+        assert!(value.get("require_canonical").is_none());
+    }
+
+    #[test]
+    fn block_param_plain_hash_does_not_set_require_canonical() {
+        // A hash with `require_canonical: false` takes the plain `BlockId` path, not the
+        // `requireCanonical`-bearing `Canonical` variant.
+        let param = BlockParam::from(Some(BlockSpec::from(H256::repeat_byte(0x22))));
+        let value = serde_json::to_value(&param).unwrap();
+        assert!(value.get("requireCanonical").is_none());
+    }
+
+    #[test]
+    fn block_param_none_defaults_to_latest() {
+        let value = serde_json::to_value(BlockParam::from(None)).unwrap();
+        assert_eq!(value, serde_json::json!("latest"));
+    }
+
+    fn test_error(message: &str) -> std::io::Error {
+        std::io::Error::new(std::io::ErrorKind::Other, message.to_string())
+    }
+
+    #[test]
+    fn parse_retry_after_extracts_seconds() {
+        let err = test_error("429 Too Many Requests (Retry-After: 17)");
+        assert_eq!(parse_retry_after(&err), Some(Duration::from_secs(17)));
+    }
+
+    #[test]
+    fn parse_retry_after_absent_returns_none() {
+        let err = test_error("connection reset by peer");
+        assert_eq!(parse_retry_after(&err), None);
+    }
+
+    #[test]
+    fn looks_retryable_matches_known_transient_errors() {
+        assert!(looks_retryable(&test_error("received 429 from upstream")));
+    }
+
+    #[test]
+    fn looks_retryable_rejects_application_errors() {
+        assert!(!looks_retryable(&test_error("execution reverted: insufficient balance")));
     }
 }